@@ -0,0 +1,214 @@
+// Local SQLite-backed cache of search results and saved books.
+
+use rusqlite::{params, Connection, Result};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A saved reading-list entry.
+pub struct SavedBook {
+    pub isbn: String,
+    pub title: String,
+    pub author: String,
+    pub ol_key: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Local store, bundled as a SQLite file in the user's config directory, that
+/// caches `search_*` responses and persists the user's reading list so
+/// repeated queries don't re-hit the network.
+///
+/// `Connection` isn't `Sync` (it caches prepared statements behind a
+/// `RefCell`), so the connection is kept behind a `Mutex` to let `Cache` be
+/// shared across threads, e.g. the TUI's `tokio::spawn`ed tasks.
+pub struct Cache {
+    conn: Mutex<Connection>,
+}
+
+impl Cache {
+    /// Opens (creating if needed) the bundled SQLite file in the user's
+    /// config directory, e.g. `~/.config/novel_search/cache.db` on Linux.
+    pub fn open() -> Result<Cache> {
+        let path = Cache::db_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).ok();
+        }
+        Cache::from_connection(Connection::open(path)?)
+    }
+
+    /// Shared setup behind `open()`, factored out so tests can point it at
+    /// an in-memory connection instead of a file on disk.
+    fn from_connection(conn: Connection) -> Result<Cache> {
+        conn.execute(
+            // `limit_val` is part of the cache key alongside `search_type`
+            // and `query`, not necessarily the user's `--limit`: for
+            // title/author searches (see `BookSearch::fetch_next_page`) it's
+            // actually the page number being fetched, since those cache one
+            // page at a time.
+            "CREATE TABLE IF NOT EXISTS search_cache (
+                search_type TEXT NOT NULL,
+                query       TEXT NOT NULL,
+                limit_val   INTEGER NOT NULL,
+                response    TEXT NOT NULL,
+                fetched_at  INTEGER NOT NULL,
+                PRIMARY KEY (search_type, query, limit_val)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS reading_list (
+                isbn    TEXT PRIMARY KEY,
+                title   TEXT NOT NULL,
+                author  TEXT NOT NULL,
+                ol_key  TEXT,
+                url     TEXT
+            )",
+            [],
+        )?;
+        Ok(Cache { conn: Mutex::new(conn) })
+    }
+
+    fn db_path() -> PathBuf {
+        let mut dir = dirs::config_dir().unwrap_or_else(std::env::temp_dir);
+        dir.push("novel_search");
+        dir.push("cache.db");
+        dir
+    }
+
+    /// Returns the cached response for `(search_type, query, limit)` if one
+    /// exists and is younger than `ttl_secs`.
+    pub fn get(&self, search_type: &str, query: &str, limit: i32, ttl_secs: u64) -> Option<Value> {
+        let conn = self.conn.lock().unwrap();
+        let row: Result<(String, i64)> = conn.query_row(
+            "SELECT response, fetched_at FROM search_cache
+             WHERE search_type = ?1 AND query = ?2 AND limit_val = ?3",
+            params![search_type, query, limit],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        );
+        let (response, fetched_at) = row.ok()?;
+        if now_secs().saturating_sub(fetched_at as u64) < ttl_secs {
+            serde_json::from_str(&response).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Stores (or replaces) the response for `(search_type, query, limit)`,
+    /// stamped with the current time.
+    pub fn put(&self, search_type: &str, query: &str, limit: i32, response: &Value) {
+        let conn = self.conn.lock().unwrap();
+        let _ = conn.execute(
+            "INSERT OR REPLACE INTO search_cache (search_type, query, limit_val, response, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![search_type, query, limit, response.to_string(), now_secs() as i64],
+        );
+    }
+
+    /// Inserts or replaces a reading-list entry keyed by ISBN.
+    pub fn save_book(&self, book: &SavedBook) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO reading_list (isbn, title, author, ol_key, url)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![book.isbn, book.title, book.author, book.ol_key, book.url],
+        )?;
+        Ok(())
+    }
+
+    /// Returns every saved reading-list entry.
+    pub fn list_books(&self) -> Result<Vec<SavedBook>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT isbn, title, author, ol_key, url FROM reading_list")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SavedBook {
+                isbn: row.get(0)?,
+                title: row.get(1)?,
+                author: row.get(2)?,
+                ol_key: row.get(3)?,
+                url: row.get(4)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Removes a reading-list entry by ISBN, returning the number of rows removed.
+    pub fn remove_book(&self, isbn: &str) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM reading_list WHERE isbn = ?1", params![isbn])
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_cache() -> Cache {
+        Cache::from_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn get_misses_when_nothing_is_cached() {
+        let cache = test_cache();
+        assert!(cache.get("title", "dune", 10, 3600).is_none());
+    }
+
+    #[test]
+    fn put_then_get_hits_within_the_ttl() {
+        let cache = test_cache();
+        let response = json!({ "numFound": 1 });
+        cache.put("title", "dune", 10, &response);
+        assert_eq!(cache.get("title", "dune", 10, 3600), Some(response));
+    }
+
+    #[test]
+    fn get_misses_once_the_entry_is_older_than_the_ttl() {
+        let cache = test_cache();
+        let response = json!({ "numFound": 1 });
+        let stale_fetched_at = now_secs() as i64 - 10_000;
+        {
+            let conn = cache.conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO search_cache (search_type, query, limit_val, response, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params!["title", "dune", 10, response.to_string(), stale_fetched_at],
+            ).unwrap();
+        }
+        assert!(cache.get("title", "dune", 10, 60).is_none());
+    }
+
+    #[test]
+    fn save_list_and_remove_book_round_trip() {
+        let cache = test_cache();
+        let book = SavedBook {
+            isbn: "9780441013593".to_string(),
+            title: "Dune".to_string(),
+            author: "Frank Herbert".to_string(),
+            ol_key: Some("/works/OL893415W".to_string()),
+            url: None,
+        };
+        cache.save_book(&book).unwrap();
+
+        let saved = cache.list_books().unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].isbn, "9780441013593");
+        assert_eq!(saved[0].title, "Dune");
+
+        assert_eq!(cache.remove_book("9780441013593").unwrap(), 1);
+        assert!(cache.list_books().unwrap().is_empty());
+    }
+
+    #[test]
+    fn remove_book_reports_zero_rows_for_an_unknown_isbn() {
+        let cache = test_cache();
+        assert_eq!(cache.remove_book("nope").unwrap(), 0);
+    }
+}
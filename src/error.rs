@@ -0,0 +1,87 @@
+// Typed errors for the network/parsing layer, so callers can tell "no
+// results" apart from "network down" apart from "rate limited".
+
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Network(reqwest::Error),
+    Parse(serde_json::Error),
+    Io(std::io::Error),
+    Cache(String),
+    NotFound { kind: String, query: String },
+    RateLimited,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Network(e) => write!(f, "network error: {}", e),
+            Error::Parse(e) => write!(f, "failed to parse response: {}", e),
+            Error::Io(e) => write!(f, "i/o error: {}", e),
+            Error::Cache(message) => write!(f, "reading list error: {}", message),
+            Error::NotFound { kind, query } => write!(f, "no {} found for: {}", kind, query),
+            Error::RateLimited => write!(f, "rate limited by Open Library, try again later"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::Cache(_) | Error::NotFound { .. } | Error::RateLimited => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Network(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Cache(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_error_converts_and_displays_its_source() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "missing file");
+        let err: Error = io_err.into();
+        assert!(matches!(err, Error::Io(_)));
+        assert_eq!(err.to_string(), "i/o error: missing file");
+    }
+
+    #[test]
+    fn not_found_displays_kind_and_query() {
+        let err = Error::NotFound { kind: "ISBN".to_string(), query: "0-00-000000-0".to_string() };
+        assert_eq!(err.to_string(), "no ISBN found for: 0-00-000000-0");
+    }
+
+    #[test]
+    fn rusqlite_error_converts_to_cache() {
+        let err: Error = rusqlite::Error::QueryReturnedNoRows.into();
+        assert!(matches!(err, Error::Cache(_)));
+    }
+}
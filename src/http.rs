@@ -0,0 +1,120 @@
+// Shared HTTP client with polite rate limiting and retry/backoff for
+// talking to Open Library.
+
+use crate::error::Error;
+use reqwest::{Client as ReqwestClient, Response, StatusCode};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+const USER_AGENT: &str = concat!(
+    "novel_search/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/SpeedyGo55/novel_search)"
+);
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_RETRIES: u32 = 5;
+
+/// A shared client that throttles requests to Open Library to a minimum
+/// interval and retries 429/5xx responses with exponential backoff, so
+/// multi-page pulls stop getting blocked.
+pub struct Client {
+    inner: ReqwestClient,
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Client::with_min_interval(DEFAULT_MIN_INTERVAL)
+    }
+
+    pub fn with_min_interval(min_interval: Duration) -> Self {
+        let inner = ReqwestClient::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("failed to build HTTP client");
+        Client {
+            inner,
+            min_interval,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    async fn throttle(&self) {
+        let wait = {
+            let mut last_request = self.last_request.lock().unwrap();
+            let now = Instant::now();
+            let start = last_request.map_or(now, |last| (last + self.min_interval).max(now));
+            *last_request = Some(start);
+            start - now
+        };
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+    }
+
+    /// GETs `url`, enforcing the minimum request interval beforehand and
+    /// retrying on 429/5xx responses with exponential backoff. Once
+    /// `MAX_RETRIES` is exhausted, a persistent 429 becomes `Error::RateLimited`;
+    /// a persistent 5xx is returned as-is for the caller to inspect.
+    pub async fn get(&self, url: &str) -> Result<Response, Error> {
+        let mut attempt = 0;
+        loop {
+            self.throttle().await;
+            let response = self.inner.get(url).send().await?;
+            let status = response.status();
+            if !is_retryable(status) {
+                return Ok(response);
+            }
+            if attempt >= MAX_RETRIES {
+                if status == StatusCode::TOO_MANY_REQUESTS {
+                    return Err(Error::RateLimited);
+                }
+                return Ok(response);
+            }
+            attempt += 1;
+            sleep(backoff_duration(attempt)).await;
+        }
+    }
+}
+
+/// Whether a response status should be retried: 429 (rate limited) or any 5xx.
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Exponential backoff, doubling per attempt and capped at 32s (`attempt` of 5+).
+fn backoff_duration(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt.min(5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_retryable_is_true_for_429_and_5xx() {
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[test]
+    fn is_retryable_is_false_for_success_and_other_4xx() {
+        assert!(!is_retryable(StatusCode::OK));
+        assert!(!is_retryable(StatusCode::NOT_FOUND));
+        assert!(!is_retryable(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn backoff_duration_doubles_per_attempt_and_caps_at_32_seconds() {
+        assert_eq!(backoff_duration(1), Duration::from_secs(2));
+        assert_eq!(backoff_duration(2), Duration::from_secs(4));
+        assert_eq!(backoff_duration(3), Duration::from_secs(8));
+        assert_eq!(backoff_duration(5), Duration::from_secs(32));
+        assert_eq!(backoff_duration(8), Duration::from_secs(32));
+    }
+}
@@ -1,16 +1,55 @@
 // A CLI-tool to browse books from the Open Library API
 // Features: search by name, search by author, search by ISBN, search by text, random book, top book by genre
 
-use reqwest;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
 use serde_json::Value;
 use rand::random;
 
+mod db;
+mod error;
+mod http;
+mod tui;
+use db::{Cache, SavedBook};
+use error::Error;
+use http::Client as HttpClient;
+
 #[derive(Parser, Debug)]
 #[command(author = "SpeedyGo55", version, about = "A simple CLI-tool to browse books from the Open Library API", name = "novel_search")]
 struct Args {
     #[command(subcommand)]
     cmd: Commands,
+    /// How long cached search results stay valid, in seconds
+    #[arg(long, global = true, default_value = "3600")]
+    cache_ttl: u64,
+    /// Output format for search results
+    #[arg(long, global = true, value_enum, default_value = "pretty")]
+    format: OutputFormat,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+enum OutputFormat {
+    /// Human-readable text blocks (the default)
+    Pretty,
+    /// A single JSON array
+    Json,
+    /// Newline-delimited JSON, one book per line
+    Ndjson,
+    /// Comma-separated values
+    Csv,
+}
+
+/// A book normalized from any search path's raw `serde_json::Value`, ready
+/// to hand to `render` regardless of output format.
+#[derive(Debug, Clone, Serialize)]
+struct Book {
+    title: String,
+    authors: Vec<String>,
+    isbn: Option<String>,
+    key: Option<String>,
+    url: Option<String>,
+    subject: Option<String>,
+    year: Option<i64>,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -19,6 +58,37 @@ enum Commands {
     Search(Search),
     /// Get a random book from a genre
     Random(Random),
+    /// Save a book to your reading list by ISBN
+    Save(Save),
+    /// List the books in your reading list
+    List,
+    /// Remove a book from your reading list by ISBN
+    Remove(Remove),
+    /// Fetch a book's cover image by ISBN or Open Library key
+    Covers(Covers),
+    /// Launch an interactive terminal browser for search results
+    Browse,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Covers {
+    /// The ISBN or Open Library key (e.g. OL123456M) of the book
+    id: String,
+    /// Path to save the cover image to. If omitted, prints the cover URL instead
+    #[arg(short, long)]
+    out: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Save {
+    /// The ISBN of the book to save
+    isbn: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Remove {
+    /// The ISBN of the book to remove
+    isbn: String,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -32,6 +102,9 @@ struct Search {
     /// The number of results to return (not applicable for ISBN search)
     #[arg(short, long, default_value = "2")]
     limit: i32,
+    /// Walk every page of results instead of stopping at `limit`
+    #[arg(long)]
+    all: bool,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -44,8 +117,10 @@ struct Random {
 enum SearchType {
     /// Search by name
     Name,
+    /// Search by author
+    Author,
     /// Search by ISBN
-    ISBN,
+    Isbn,
     /// Search by subject
     Subject,
 }
@@ -53,212 +128,639 @@ enum SearchType {
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
+    let cache = Cache::open().ok();
+    let http = HttpClient::new();
+
+    if let Err(e) = run(args, cache, http).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(match e {
+            Error::NotFound { .. } => 1,
+            Error::RateLimited => 2,
+            Error::Network(_) => 3,
+            Error::Parse(_) => 4,
+            Error::Io(_) => 5,
+            Error::Cache(_) => 6,
+        });
+    }
+}
 
+async fn run(args: Args, cache: Option<Cache>, http: HttpClient) -> Result<(), Error> {
     match args.cmd {
         Commands::Search(search) => {
             match search.search_type {
                 SearchType::Name => {
-                    let api_response = search_name(&search.data, search.limit).await;
-                    display_books(api_response);
+                    let limit = if search.all { None } else { Some(search.limit) };
+                    let results = BookSearch::new(&search.data, limit, "title", cache.as_ref(), &http);
+                    let books = collect_books(results, args.cache_ttl).await?;
+                    render(books, &args.format);
+                },
+                SearchType::Author => {
+                    let limit = if search.all { None } else { Some(search.limit) };
+                    let results = BookSearch::new(&search.data, limit, "author", cache.as_ref(), &http);
+                    let books = collect_books(results, args.cache_ttl).await?;
+                    render(books, &args.format);
                 },
-                SearchType::ISBN => {
-                    let api_response = search_isbn(&search.data).await;
-                    display_isbn_books(api_response);
+                SearchType::Isbn => {
+                    let api_response = search_isbn(&search.data, &http, cache.as_ref(), args.cache_ttl).await?;
+                    render(books_from_isbn_response(&api_response), &args.format);
                 },
                 SearchType::Subject => {
-                    let api_response = search_subject(&search.data, search.limit).await;
-                    display_subject_titles(api_response);
+                    let api_response = search_subject(&search.data, search.limit, &http, cache.as_ref(), args.cache_ttl).await?;
+                    render(books_from_subject_response(&api_response, &search.data), &args.format);
                 }
             }
         },
         Commands::Random(random) => {
-            let api_response = random_book(&random.genre).await;
-            let title = get_random_book_title(api_response);
-            let api_response = search_name(&title, 1).await;
-            display_books(api_response);
+            let api_response = random_book(&random.genre, &http).await?;
+            let title = get_random_book_title(api_response)?;
+            let results = BookSearch::new(&title, Some(1), "title", cache.as_ref(), &http);
+            let books = collect_books(results, args.cache_ttl).await?;
+            render(books, &args.format);
+        },
+        Commands::Covers(covers) => {
+            fetch_cover(&covers.id, covers.out.as_deref(), &http).await?;
+        },
+        Commands::Browse => {
+            tui::run(http, cache, args.cache_ttl);
+        },
+        Commands::Save(save) => {
+            let api_response = search_isbn(&save.isbn, &http, cache.as_ref(), args.cache_ttl).await?;
+            save_book(api_response, &save.isbn, cache.as_ref())?;
+        },
+        Commands::List => {
+            list_books(cache.as_ref())?;
+        },
+        Commands::Remove(remove) => {
+            remove_book(&remove.isbn, cache.as_ref())?;
         }
     }
 
+    Ok(())
+}
+
+/// A lazily-paginated cursor over `/search.json` results.
+///
+/// Keeps the current page's `docs` in `batch` and refills it from the next
+/// page once drained, so callers can walk arbitrarily many results (or all
+/// of them, via `--all`) without one giant upfront request.
+struct BookSearch<'a> {
+    field: &'static str,
+    name: String,
+    batch: Vec<Value>,
+    page: i32,
+    offset: i32,
+    num_found: i32,
+    limit: Option<i32>,
+    cache: Option<&'a Cache>,
+    http: &'a HttpClient,
 }
 
-async fn search_name(name: &str, limit: i32) -> Value {
-    let url = format!("https://openlibrary.org/search.json?title={}&limit={}", name, limit);
-    let response = reqwest::get(&url).await;
-    let response = match response {
-        Ok(response) => response,
-        Err(_) => {
-            println!("No books found with the name: {}", name);
-            std::process::exit(1);
+const SEARCH_PAGE_SIZE: i32 = 50;
+
+/// A `BookSearch` cursor's position, cheap to snapshot and restore so a
+/// caller can resume paging across calls without holding the cursor (and
+/// its borrowed `Cache`/`HttpClient`) alive the whole time.
+#[derive(Debug, Clone, Copy, Default)]
+struct BookSearchState {
+    page: i32,
+    offset: i32,
+    num_found: i32,
+}
+
+impl<'a> BookSearch<'a> {
+    /// `field` is the `/search.json` query param to search on, e.g. `"title"` or `"author"`.
+    fn new(name: &str, limit: Option<i32>, field: &'static str, cache: Option<&'a Cache>, http: &'a HttpClient) -> Self {
+        BookSearch::resume(name, limit, field, cache, http, BookSearchState::default())
+    }
+
+    /// Rebuilds a cursor at a previously `state()`-captured position, so a
+    /// caller can page forward across separate calls (e.g. UI event
+    /// handlers) without keeping the cursor itself alive in between.
+    fn resume(name: &str, limit: Option<i32>, field: &'static str, cache: Option<&'a Cache>, http: &'a HttpClient, state: BookSearchState) -> Self {
+        BookSearch {
+            field,
+            name: name.to_string(),
+            batch: Vec::new(),
+            page: state.page,
+            offset: state.offset,
+            num_found: if state.page == 0 { i32::MAX } else { state.num_found },
+            limit,
+            cache,
+            http,
         }
-    };
-    let api_response = response.json().await;
-    let api_response = match api_response {
-        Ok(api_response) => api_response,
-        Err(_) => {
-            println!("No books found with the name: {}", name);
-            std::process::exit(1);
+    }
+
+    /// Snapshots the cursor's current position for a later `resume()`.
+    fn state(&self) -> BookSearchState {
+        BookSearchState { page: self.page, offset: self.offset, num_found: self.num_found }
+    }
+
+    /// Whether another page can still be fetched.
+    fn has_more(&self) -> bool {
+        self.page == 0 || self.offset < self.num_found
+    }
+
+    /// Fetches exactly one page (reusing the cache, same as `next()`) and
+    /// returns its docs, for callers that page forward explicitly instead
+    /// of draining to a `limit`.
+    async fn fetch_page(&mut self, cache_ttl: u64) -> Result<Vec<Value>, Error> {
+        self.fetch_next_page(cache_ttl).await?;
+        self.offset += self.batch.len() as i32;
+        Ok(std::mem::take(&mut self.batch))
+    }
+
+    async fn fetch_next_page(&mut self, cache_ttl: u64) -> Result<(), Error> {
+        let next_page = self.page + 1;
+        // `Cache::get`/`put`'s `limit` parameter doubles as "the page being
+        // fetched" for title/author searches, since we cache one page of
+        // `/search.json` results at a time rather than the user's `--limit`.
+        if let Some(cache) = self.cache {
+            if let Some(cached) = cache.get(self.field, &self.name, next_page, cache_ttl) {
+                self.page = next_page;
+                self.num_found = cached["numFound"].as_i64().unwrap_or(0) as i32;
+                self.batch = cached["docs"].as_array().cloned().unwrap_or_default();
+                return Ok(());
+            }
         }
-    };
-    api_response
-}
+        self.page = next_page;
+        let url = format!(
+            "https://openlibrary.org/search.json?{}={}&page={}&limit={}",
+            self.field, self.name, self.page, SEARCH_PAGE_SIZE
+        );
+        let response = self.http.get(&url).await?;
+        let api_response: Value = response.json().await?;
+        if self.page == 1 && api_response["docs"].as_array().is_none_or(|docs| docs.is_empty()) {
+            return Err(Error::NotFound { kind: self.field.to_string(), query: self.name.clone() });
+        }
+        if let Some(cache) = self.cache {
+            cache.put(self.field, &self.name, self.page, &api_response);
+        }
+        self.num_found = api_response["numFound"].as_i64().unwrap_or(0) as i32;
+        self.batch = api_response["docs"].as_array().cloned().unwrap_or_default();
+        Ok(())
+    }
 
-async fn search_isbn(isbn: &str) -> Value {
-    let url = format!("https://openlibrary.org/api/volumes/brief/isbn/{}.json", isbn);
-    let response = reqwest::get(&url).await;
-    let response = match response {
-        Ok(response) => response,
-        Err(_) => {
-            println!("No books found with the ISBN: {}", isbn);
-            std::process::exit(1);
+    /// Pops the next result, fetching another page first if the current
+    /// batch is drained and more results remain within the user limit.
+    async fn next(&mut self, cache_ttl: u64) -> Result<Option<Value>, Error> {
+        if let Some(limit) = self.limit {
+            if self.offset >= limit {
+                return Ok(None);
+            }
         }
-    };
-    let api_response = response.json().await;
-    let api_response = match api_response {
-        Ok(api_response) => api_response,
-        Err(_) => {
-            println!("No books found with the ISBN: {}", isbn);
-            std::process::exit(1);
+        if self.batch.is_empty() {
+            if self.offset > 0 && self.offset >= self.num_found {
+                return Ok(None);
+            }
+            self.fetch_next_page(cache_ttl).await?;
+            if self.batch.is_empty() {
+                return Ok(None);
+            }
         }
-    };
-    api_response
+        self.offset += 1;
+        Ok(Some(self.batch.remove(0)))
+    }
 }
 
-async fn search_subject(subject: &str, limit: i32) -> Value {
-    let url = format!("https://openlibrary.org/subjects/{}.json?limit={}", subject.to_lowercase(), limit);
-    let response = reqwest::get(&url).await;
-    let response = match response {
-        Ok(response) => response,
-        Err(_) => {
-            println!("No books found with the subject: {}", subject);
-            std::process::exit(1);
+async fn search_isbn(isbn: &str, http: &HttpClient, cache: Option<&Cache>, cache_ttl: u64) -> Result<Value, Error> {
+    if let Some(cache) = cache {
+        if let Some(cached) = cache.get("isbn", isbn, 0, cache_ttl) {
+            return Ok(cached);
         }
-    };
-    let text = response.text().await;
-    let api_response = serde_json::from_str(&text.unwrap());
-    let api_response = match api_response {
-        Ok(api_response) => api_response,
-        Err(e) => {
-            println!("No books found with the subject: {}", subject);
-            println!("{}", e);
-            std::process::exit(1);
+    }
+    let url = format!("https://openlibrary.org/api/volumes/brief/isbn/{}.json", isbn);
+    let response = http.get(&url).await?;
+    let api_response: Value = response.json().await?;
+    if api_response["items"].as_array().is_none_or(|items| items.is_empty()) {
+        return Err(Error::NotFound { kind: "ISBN".to_string(), query: isbn.to_string() });
+    }
+    if let Some(cache) = cache {
+        cache.put("isbn", isbn, 0, &api_response);
+    }
+    Ok(api_response)
+}
+
+async fn search_subject(subject: &str, limit: i32, http: &HttpClient, cache: Option<&Cache>, cache_ttl: u64) -> Result<Value, Error> {
+    if let Some(cache) = cache {
+        if let Some(cached) = cache.get("subject", subject, limit, cache_ttl) {
+            return Ok(cached);
         }
-    };
-    api_response
+    }
+    let url = format!("https://openlibrary.org/subjects/{}.json?limit={}", subject.to_lowercase(), limit);
+    let response = http.get(&url).await?;
+    let text = response.text().await?;
+    let api_response: Value = serde_json::from_str(&text)?;
+    if api_response["works"].as_array().is_none_or(|works| works.is_empty()) {
+        return Err(Error::NotFound { kind: "subject".to_string(), query: subject.to_string() });
+    }
+    if let Some(cache) = cache {
+        cache.put("subject", subject, limit, &api_response);
+    }
+    Ok(api_response)
 }
 
-async fn random_book(genre: &str) -> Value {
+async fn random_book(genre: &str, http: &HttpClient) -> Result<Value, Error> {
     let limit = random::<u8>();
     let offset = random::<u8>();
     let url = format!("https://openlibrary.org/subjects/{}.json?limit={}&offset={}", genre, limit, offset);
-    let response = reqwest::get(&url).await;
-    let response = match response {
-        Ok(response) => response,
-        Err(_) => {
-            println!("No books found with the genre: {}", genre);
-            std::process::exit(1);
-        }
-    };
-    let text = response.text().await;
-    let api_response = serde_json::from_str(&text.unwrap());
-    let api_response = match api_response {
-        Ok(api_response) => api_response,
-        Err(_) => {
-            println!("No books found with the genre: {}", genre);
-            std::process::exit(1);
+    let response = http.get(&url).await?;
+    let text = response.text().await?;
+    let api_response: Value = serde_json::from_str(&text)?;
+    if api_response["works"].as_array().is_none_or(|works| works.is_empty()) {
+        return Err(Error::NotFound { kind: "genre".to_string(), query: genre.to_string() });
+    }
+    Ok(api_response)
+}
+
+/// Drains a `BookSearch` cursor into a flat `Vec<Book>`, skipping docs with
+/// no title.
+async fn collect_books(mut search: BookSearch<'_>, cache_ttl: u64) -> Result<Vec<Book>, Error> {
+    let mut books = Vec::new();
+    while let Some(doc) = search.next(cache_ttl).await? {
+        if let Some(book) = book_from_search_doc(&doc) {
+            books.push(book);
         }
+    }
+    Ok(books)
+}
+
+fn book_from_search_doc(doc: &Value) -> Option<Book> {
+    let title = doc["title"].as_str()?.to_string();
+    let authors = doc["author_name"].as_array()
+        .map(|authors| authors.iter().filter_map(|a| a.as_str().map(str::to_string)).collect())
+        .unwrap_or_else(|| vec!["Unknown".to_string()]);
+    let key = doc["key"].as_str().map(str::to_string);
+    let url = key.as_ref().map(|key| format!("https://openlibrary.org{}", key));
+    let isbn = doc["isbn"].as_array().and_then(|isbn| isbn.first()).and_then(|v| v.as_str()).map(str::to_string);
+    let year = doc["first_publish_year"].as_i64();
+    Some(Book { title, authors, isbn, key, url, subject: None, year })
+}
+
+fn books_from_isbn_response(api_response: &Value) -> Vec<Book> {
+    let items = match api_response["items"].as_array() {
+        Some(items) => items,
+        None => return Vec::new(),
     };
-    api_response
+    items.iter().filter_map(|item| {
+        let url = item["itemURL"].as_str().map(str::to_string);
+        let from_record = item["fromRecord"].as_str()?;
+        let data = &api_response["records"][from_record]["data"];
+        let title = data["title"].as_str()?.to_string();
+        let authors = data["authors"].as_array()
+            .map(|authors| authors.iter().filter_map(|a| a["name"].as_str().map(str::to_string)).collect())
+            .unwrap_or_else(|| vec!["Unknown".to_string()]);
+        let isbn = data["identifiers"]["isbn_10"].as_str()
+            .or_else(|| data["identifiers"]["isbn_13"].as_str())
+            .map(str::to_string);
+        let key = data["key"].as_str().map(str::to_string);
+        Some(Book { title, authors, isbn, key, url, subject: None, year: None })
+    }).collect()
 }
 
-fn display_books(api_response: Value) {
-    let docs = api_response["docs"].as_array();
-    let docs = match docs {
-        Some(docs) => docs,
-        None => {
-            api_response["works"].as_array().unwrap()
-        }
+fn books_from_subject_response(api_response: &Value, subject: &str) -> Vec<Book> {
+    let works = match api_response["works"].as_array() {
+        Some(works) => works,
+        None => return Vec::new(),
     };
-    println!("Found {} books", docs.len());
-    println!("{}", "-".repeat(50));
-    println!("{}", "-".repeat(50));
-    for doc in docs {
-        let title = doc["title"].as_str();
-        let title = match title {
-            Some(title) => title,
-            None => continue
-        };
-        let author = doc["author_name"].as_array();
-        let author = match author {
-            Some(author) => author,
-            None => &{
-                vec![Value::String("Unknown".to_string())]
-            }
-        };
-        let author = author[0].as_str();
-        let author = match author {
-            Some(author) => author,
-            None => "Unknown"
-        };
-        let isbn = doc["isbn"].as_array();
-        let isbn = match isbn {
-            Some(isbn) => isbn,
-            None => &{
-                vec![Value::String("Unknown".to_string())]
+    works.iter().filter_map(|work| {
+        let title = work["title"].as_str()?.to_string();
+        let authors = work["authors"].as_array()
+            .map(|authors| authors.iter().filter_map(|a| a["name"].as_str().map(str::to_string)).collect())
+            .unwrap_or_else(|| vec!["Unknown".to_string()]);
+        let key = work["key"].as_str().map(str::to_string);
+        let url = key.as_ref().map(|key| format!("https://openlibrary.org{}", key));
+        let year = work["first_publish_year"].as_i64();
+        Some(Book { title, authors, isbn: None, key, url, subject: Some(subject.to_string()), year })
+    }).collect()
+}
+
+/// Renders `books` in the requested output format.
+fn render(books: Vec<Book>, format: &OutputFormat) {
+    match format {
+        OutputFormat::Pretty => render_pretty(&books),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&books).unwrap()),
+        OutputFormat::Ndjson => {
+            for book in &books {
+                println!("{}", serde_json::to_string(book).unwrap());
             }
-        };
-        let key = doc["key"].as_str().unwrap();
-        let url = format!("https://openlibrary.org{}", key);
-        let isbn = isbn[0].as_str().unwrap();
-        println!("Title: {}", title);
-        println!("Author: {}", author);
-        println!("ISBN: {}", isbn);
-        println!("URL: {}", url);
+        },
+        OutputFormat::Csv => render_csv(&books),
+    }
+}
+
+fn render_pretty(books: &[Book]) {
+    println!("{}", "-".repeat(50));
+    for book in books {
+        println!("Title: {}", book.title);
+        println!("Author: {}", book.authors.join(", "));
+        if let Some(isbn) = &book.isbn {
+            println!("ISBN: {}", isbn);
+        }
+        if let Some(url) = &book.url {
+            println!("URL: {}", url);
+        }
+        if let Some(subject) = &book.subject {
+            println!("Subject: {}", subject);
+        }
+        if let Some(year) = book.year {
+            println!("Year: {}", year);
+        }
         println!("{}", "-".repeat(50));
     }
+    println!("Found {} books", books.len());
     println!("{}", "-".repeat(50));
 }
 
-fn display_subject_titles(api_response: Value) {
-    let works = api_response["works"].as_array().unwrap();
-    for work in works {
-        let title = work["title"].as_str().unwrap();
-        println!("Title: {}", title);
-        println!();
+fn render_csv(books: &[Book]) {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    let _ = writer.write_record(["title", "authors", "isbn", "key", "url", "subject", "year"]);
+    for book in books {
+        let _ = writer.write_record([
+            book.title.clone(),
+            book.authors.join("; "),
+            book.isbn.clone().unwrap_or_default(),
+            book.key.clone().unwrap_or_default(),
+            book.url.clone().unwrap_or_default(),
+            book.subject.clone().unwrap_or_default(),
+            book.year.map(|year| year.to_string()).unwrap_or_default(),
+        ]);
     }
+    let _ = writer.flush();
 }
 
-fn display_isbn_books(api_response: Value) {
-    let items = api_response["items"].as_array().unwrap();
-    println!("Found {} matches", items.len());
-    println!("{}", "-".repeat(50));
+fn get_random_book_title(api_response: Value) -> Result<String, Error> {
+    let not_found = || Error::NotFound { kind: "genre".to_string(), query: "random selection".to_string() };
+    let books = api_response["works"].as_array().filter(|books| !books.is_empty()).ok_or_else(not_found)?;
+    let book = books[(random::<u32>() % books.len() as u32) as usize].clone();
+    let title = book["title"].as_str().ok_or_else(not_found)?;
+    Ok(title.to_string())
+}
+
+fn no_cache_error() -> Error {
+    Error::Cache("could not open the local reading list database".to_string())
+}
+
+fn save_book(api_response: Value, isbn: &str, cache: Option<&Cache>) -> Result<(), Error> {
+    let cache = cache.ok_or_else(no_cache_error)?;
+    let items = api_response["items"].as_array();
+    let item = items.and_then(|items| items.first())
+        .ok_or_else(|| Error::NotFound { kind: "ISBN".to_string(), query: isbn.to_string() })?;
+    let from_record = item["fromRecord"].as_str().unwrap();
+    let data = api_response["records"][from_record]["data"].clone();
+    let title = data["title"].as_str().unwrap_or("Unknown").to_string();
+    let author = data["authors"].as_array()
+        .and_then(|authors| authors.first())
+        .and_then(|author| author["name"].as_str())
+        .unwrap_or("Unknown")
+        .to_string();
+    let ol_key = data["key"].as_str().map(|s| s.to_string());
+    let url = item["itemURL"].as_str().map(|s| s.to_string());
+    let book = SavedBook {
+        isbn: isbn.to_string(),
+        title: title.clone(),
+        author,
+        ol_key,
+        url,
+    };
+    cache.save_book(&book)?;
+    println!("Saved \"{}\" to your reading list", title);
+    Ok(())
+}
+
+fn list_books(cache: Option<&Cache>) -> Result<(), Error> {
+    let cache = cache.ok_or_else(no_cache_error)?;
+    let books = cache.list_books()?;
+    println!("Found {} saved books", books.len());
     println!("{}", "-".repeat(50));
-    for item in items {
-        let url = item["itemURL"].as_str().unwrap();
-        let from_record = item["fromRecord"].as_str().unwrap();
-        let records = api_response["records"][from_record].clone();
-        let data = records["data"].clone();
-        let title = data["title"].as_str().unwrap();
-        let authors = data["authors"].as_array().unwrap();
-        let author_names = authors.iter().map(|author| author["name"].as_str().unwrap()).collect::<Vec<&str>>();
-        let author_names = author_names.join(", ");
-        let isbn = data["identifiers"]["isbn_10"].as_str();
-        let isbn = match isbn {
-            Some(isbn) => isbn,
-            None => data["identifiers"]["isbn_13"].as_str().unwrap_or("Unknown")
-        };
-        println!("Title: {}", title);
-        println!("Authors: {}", author_names);
-        println!("ISBN: {}", isbn);
-        println!("URL: {}", url);
+    for book in books {
+        println!("Title: {}", book.title);
+        println!("Author: {}", book.author);
+        println!("ISBN: {}", book.isbn);
+        if let Some(url) = book.url {
+            println!("URL: {}", url);
+        }
         println!("{}", "-".repeat(50));
     }
-    println!("{}", "-".repeat(50));
+    Ok(())
+}
 
+fn remove_book(isbn: &str, cache: Option<&Cache>) -> Result<(), Error> {
+    let cache = cache.ok_or_else(no_cache_error)?;
+    match cache.remove_book(isbn)? {
+        0 => println!("No saved book found with the ISBN: {}", isbn),
+        _ => println!("Removed {} from your reading list", isbn),
+    }
+    Ok(())
 }
 
-fn get_random_book_title(api_response: Value) -> String {
-    let books = api_response["works"].as_array().unwrap();
-    let book = books[(random::<u32>() % books.len() as u32) as usize].clone();
-    let title = book["title"].as_str().unwrap();
-    title.to_string()
+/// Resolves and either prints or downloads the cover for `id`, which may be
+/// an ISBN or an Open Library key (e.g. `OL123456M`).
+async fn fetch_cover(id: &str, out: Option<&str>, http: &HttpClient) -> Result<(), Error> {
+    let kind = if id.to_uppercase().starts_with("OL") { "olid" } else { "isbn" };
+    let url = format!("https://covers.openlibrary.org/b/{}/{}-L.jpg", kind, id);
+
+    let out = match out {
+        Some(out) => out,
+        None => {
+            println!("{}", url);
+            return Ok(());
+        }
+    };
+
+    let response = http.get(&url).await?;
+    let bytes = response.bytes().await?;
+    std::fs::write(out, &bytes)?;
+    println!("Saved cover to {}", out);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn book_from_search_doc_parses_a_typical_doc() {
+        let doc = json!({
+            "title": "The Hobbit",
+            "author_name": ["J.R.R. Tolkien"],
+            "key": "/works/OL262758W",
+            "isbn": ["9780261102217", "9780007458424"],
+            "first_publish_year": 1937,
+        });
+
+        let book = book_from_search_doc(&doc).expect("doc has a title");
+        assert_eq!(book.title, "The Hobbit");
+        assert_eq!(book.authors, vec!["J.R.R. Tolkien".to_string()]);
+        assert_eq!(book.key.as_deref(), Some("/works/OL262758W"));
+        assert_eq!(book.url.as_deref(), Some("https://openlibrary.org/works/OL262758W"));
+        assert_eq!(book.isbn.as_deref(), Some("9780261102217"));
+        assert_eq!(book.year, Some(1937));
+        assert_eq!(book.subject, None);
+    }
+
+    #[test]
+    fn book_from_search_doc_defaults_missing_author_and_fields() {
+        let doc = json!({ "title": "Anonymous Work" });
+
+        let book = book_from_search_doc(&doc).expect("doc has a title");
+        assert_eq!(book.authors, vec!["Unknown".to_string()]);
+        assert_eq!(book.key, None);
+        assert_eq!(book.url, None);
+        assert_eq!(book.isbn, None);
+        assert_eq!(book.year, None);
+    }
+
+    #[test]
+    fn book_from_search_doc_rejects_a_titleless_doc() {
+        let doc = json!({ "author_name": ["Nobody"] });
+        assert!(book_from_search_doc(&doc).is_none());
+    }
+
+    #[test]
+    fn books_from_isbn_response_parses_the_nested_record() {
+        let api_response = json!({
+            "items": [{ "itemURL": "https://openlibrary.org/books/OL1M", "fromRecord": "OL1M" }],
+            "records": {
+                "OL1M": {
+                    "data": {
+                        "title": "Dune",
+                        "authors": [{ "name": "Frank Herbert" }],
+                        "identifiers": { "isbn_13": "9780441013593" },
+                        "key": "/books/OL1M",
+                    }
+                }
+            },
+        });
+
+        let books = books_from_isbn_response(&api_response);
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].title, "Dune");
+        assert_eq!(books[0].authors, vec!["Frank Herbert".to_string()]);
+        assert_eq!(books[0].isbn.as_deref(), Some("9780441013593"));
+        assert_eq!(books[0].url.as_deref(), Some("https://openlibrary.org/books/OL1M"));
+    }
+
+    #[test]
+    fn books_from_isbn_response_handles_a_missing_items_array() {
+        let api_response = json!({});
+        assert!(books_from_isbn_response(&api_response).is_empty());
+    }
+
+    #[test]
+    fn books_from_subject_response_tags_every_book_with_the_subject() {
+        let api_response = json!({
+            "works": [{
+                "title": "Foundation",
+                "authors": [{ "name": "Isaac Asimov" }],
+                "key": "/works/OL1W",
+                "first_publish_year": 1951,
+            }],
+        });
+
+        let books = books_from_subject_response(&api_response, "science-fiction");
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].subject.as_deref(), Some("science-fiction"));
+        assert_eq!(books[0].year, Some(1951));
+        assert_eq!(books[0].isbn, None);
+    }
+
+    #[test]
+    fn books_from_subject_response_handles_a_missing_works_array() {
+        let api_response = json!({});
+        assert!(books_from_subject_response(&api_response, "anything").is_empty());
+    }
+
+    #[test]
+    fn get_random_book_title_errors_on_an_empty_works_array() {
+        let api_response = json!({ "works": [] });
+        assert!(get_random_book_title(api_response).is_err());
+    }
+
+    #[tokio::test]
+    async fn next_stops_once_the_limit_is_reached() {
+        let http = HttpClient::new();
+        let mut search = BookSearch {
+            field: "title",
+            name: "dune".to_string(),
+            batch: vec![json!({ "title": "Dune" })],
+            page: 1,
+            offset: 1,
+            num_found: 100,
+            limit: Some(1),
+            cache: None,
+            http: &http,
+        };
+        assert_eq!(search.next(3600).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn next_stops_once_num_found_is_exhausted_without_refetching() {
+        let http = HttpClient::new();
+        let mut search = BookSearch {
+            field: "title",
+            name: "dune".to_string(),
+            batch: Vec::new(),
+            page: 1,
+            offset: 5,
+            num_found: 5,
+            limit: None,
+            cache: None,
+            http: &http,
+        };
+        assert_eq!(search.next(3600).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn next_pops_from_the_current_batch_without_refetching() {
+        let http = HttpClient::new();
+        let doc = json!({ "title": "Dune" });
+        let mut search = BookSearch {
+            field: "title",
+            name: "dune".to_string(),
+            batch: vec![doc.clone()],
+            page: 1,
+            offset: 0,
+            num_found: 100,
+            limit: None,
+            cache: None,
+            http: &http,
+        };
+        assert_eq!(search.next(3600).await.unwrap(), Some(doc));
+        assert_eq!(search.offset, 1);
+        assert!(search.batch.is_empty());
+    }
+
+    #[test]
+    fn has_more_reflects_whether_the_cursor_is_exhausted() {
+        let http = HttpClient::new();
+        let fresh = BookSearch::new("dune", None, "title", None, &http);
+        assert!(fresh.has_more());
+
+        let mid_page = BookSearch::resume(
+            "dune", None, "title", None, &http,
+            BookSearchState { page: 1, offset: 10, num_found: 50 },
+        );
+        assert!(mid_page.has_more());
+
+        let exhausted = BookSearch::resume(
+            "dune", None, "title", None, &http,
+            BookSearchState { page: 2, offset: 50, num_found: 50 },
+        );
+        assert!(!exhausted.has_more());
+    }
+
+    #[test]
+    fn state_round_trips_through_resume() {
+        let http = HttpClient::new();
+        let mut search = BookSearch::new("dune", None, "title", None, &http);
+        search.page = 2;
+        search.offset = 20;
+        search.num_found = 40;
+
+        let resumed = BookSearch::resume("dune", None, "title", None, &http, search.state());
+        assert_eq!(resumed.page, 2);
+        assert_eq!(resumed.offset, 20);
+        assert_eq!(resumed.num_found, 40);
+    }
 }
@@ -0,0 +1,220 @@
+// Interactive terminal browser for search results, built on cursive.
+//
+// Reuses the same `BookSearch` cursor (and its caching) that the `search`
+// subcommand drives, resuming it across event-loop turns via `BookSearchState`
+// so "More" pages forward instead of re-fetching a single fixed batch.
+
+use crate::db::Cache;
+use crate::http::Client as HttpClient;
+use crate::{book_from_search_doc, Book, BookSearch, BookSearchState, Error};
+use cursive::traits::*;
+use cursive::views::{Dialog, EditView, LinearLayout, SelectView, TextView};
+use cursive::Cursive;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Where a browse session's `BookSearch` cursor currently is, so "More" can
+/// resume it without holding the cursor (and its borrows) alive in between.
+#[derive(Clone)]
+struct BrowseCursor {
+    query: String,
+    field: &'static str,
+    state: BookSearchState,
+    has_more: bool,
+}
+
+type UserData = (
+    Arc<HttpClient>,
+    Option<Arc<Mutex<Cache>>>,
+    u64,
+    tokio::runtime::Handle,
+    Arc<Mutex<Option<BrowseCursor>>>,
+);
+
+/// Launches an interactive terminal browser on top of `/search.json`: a text
+/// box drives a title search, results render in a scrollable list, "More"
+/// pages forward through the same cursor the `search` subcommand uses, and
+/// selecting a result can jump straight to the author's other works.
+pub fn run(http: HttpClient, cache: Option<Cache>, cache_ttl: u64) {
+    let http = Arc::new(http);
+    let cache = cache.map(|c| Arc::new(Mutex::new(c)));
+    let runtime = tokio::runtime::Handle::current();
+    let cursor = Arc::new(Mutex::new(None));
+
+    let mut siv = cursive::default();
+    siv.set_user_data::<UserData>((http, cache, cache_ttl, runtime, cursor));
+
+    let results = SelectView::<Book>::new()
+        .on_submit(show_detail)
+        .with_name("results")
+        .scrollable();
+
+    let query = EditView::new()
+        .on_submit(|siv, query| run_search(siv, query, "title"))
+        .with_name("query");
+
+    siv.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new("Search by title (press Enter):"))
+                .child(query)
+                .child(results),
+        )
+        .title("novel_search browse")
+        .button("More", more_results)
+        .button("Quit", |siv| siv.quit()),
+    );
+
+    siv.run();
+}
+
+fn run_search(siv: &mut Cursive, query: &str, field: &'static str) {
+    let query = query.to_string();
+    let (http, cache, cache_ttl, runtime, cursor_slot) = siv.user_data::<UserData>().unwrap().clone();
+    let cb_sink = siv.cb_sink().clone();
+
+    runtime.spawn(async move {
+        let result = fetch_page(&query, field, &http, cache.as_ref(), cache_ttl, BookSearchState::default()).await;
+        match result {
+            Ok((books, state, has_more)) => {
+                *cursor_slot.lock().await = Some(BrowseCursor { query, field, state, has_more });
+                let _ = cb_sink.send(Box::new(move |siv: &mut Cursive| {
+                    siv.call_on_name("results", |view: &mut SelectView<Book>| {
+                        view.clear();
+                        for book in books {
+                            let label = format!("{} — {}", book.title, book.authors.join(", "));
+                            view.add_item(label, book);
+                        }
+                    });
+                }));
+            }
+            Err(e) => {
+                *cursor_slot.lock().await = None;
+                let message = e.to_string();
+                let _ = cb_sink.send(Box::new(move |siv: &mut Cursive| {
+                    siv.call_on_name("results", |view: &mut SelectView<Book>| view.clear());
+                    siv.add_layer(Dialog::info(message).title("Search error"));
+                }));
+            }
+        }
+    });
+}
+
+fn more_results(siv: &mut Cursive) {
+    let (http, cache, cache_ttl, runtime, cursor_slot) = siv.user_data::<UserData>().unwrap().clone();
+    let cb_sink = siv.cb_sink().clone();
+
+    runtime.spawn(async move {
+        let current = cursor_slot.lock().await.clone();
+        let current = match current {
+            Some(current) if current.has_more => current,
+            Some(_) => {
+                let _ = cb_sink.send(Box::new(|siv: &mut Cursive| {
+                    siv.add_layer(Dialog::info("No more results.").title("Browse"));
+                }));
+                return;
+            }
+            None => {
+                let _ = cb_sink.send(Box::new(|siv: &mut Cursive| {
+                    siv.add_layer(Dialog::info("Search for something first.").title("Browse"));
+                }));
+                return;
+            }
+        };
+
+        match fetch_page(&current.query, current.field, &http, cache.as_ref(), cache_ttl, current.state).await {
+            Ok((books, state, has_more)) => {
+                *cursor_slot.lock().await = Some(BrowseCursor { state, has_more, ..current });
+                let _ = cb_sink.send(Box::new(move |siv: &mut Cursive| {
+                    siv.call_on_name("results", |view: &mut SelectView<Book>| {
+                        for book in books {
+                            let label = format!("{} — {}", book.title, book.authors.join(", "));
+                            view.add_item(label, book);
+                        }
+                    });
+                }));
+            }
+            Err(e) => {
+                let message = e.to_string();
+                let _ = cb_sink.send(Box::new(move |siv: &mut Cursive| {
+                    siv.add_layer(Dialog::info(message).title("Error"));
+                }));
+            }
+        }
+    });
+}
+
+fn show_detail(siv: &mut Cursive, book: &Book) {
+    let key = book.key.clone();
+    let author = book.authors.first().cloned();
+    let (http, _cache, _cache_ttl, runtime, _cursor_slot) = siv.user_data::<UserData>().unwrap().clone();
+    let cb_sink = siv.cb_sink().clone();
+
+    runtime.spawn(async move {
+        let text = match &key {
+            Some(key) => {
+                let url = format!("https://openlibrary.org{}.json", key);
+                match http.get(&url).await {
+                    Ok(response) => format_detail(&response.json::<Value>().await.unwrap_or(Value::Null)),
+                    Err(_) => "Failed to load work detail.".to_string(),
+                }
+            }
+            None => "No further detail available.".to_string(),
+        };
+        let _ = cb_sink.send(Box::new(move |siv: &mut Cursive| {
+            let mut dialog = Dialog::around(TextView::new(text))
+                .title("Work detail")
+                .button("Close", |siv| {
+                    siv.pop_layer();
+                });
+            if let Some(author) = author {
+                dialog = dialog.button(format!("More by {}", author), move |siv| {
+                    siv.pop_layer();
+                    run_search(siv, &author, "author");
+                });
+            }
+            siv.add_layer(dialog);
+        }));
+    });
+}
+
+/// Fetches one page through a resumed `BookSearch` cursor, returning the
+/// parsed books alongside the cursor's new state and whether more remain.
+async fn fetch_page(
+    query: &str,
+    field: &'static str,
+    http: &HttpClient,
+    cache: Option<&Arc<Mutex<Cache>>>,
+    cache_ttl: u64,
+    state: BookSearchState,
+) -> Result<(Vec<Book>, BookSearchState, bool), Error> {
+    let guard = match cache {
+        Some(cache) => Some(cache.lock().await),
+        None => None,
+    };
+    let mut search = BookSearch::resume(query, None, field, guard.as_deref(), http, state);
+    let docs = search.fetch_page(cache_ttl).await?;
+    let books = docs.iter().filter_map(book_from_search_doc).collect();
+    Ok((books, search.state(), search.has_more()))
+}
+
+fn format_detail(detail: &Value) -> String {
+    let description = match &detail["description"] {
+        Value::String(s) => s.clone(),
+        Value::Object(obj) => obj.get("value").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        _ => String::new(),
+    };
+    let subjects = detail["subjects"]
+        .as_array()
+        .map(|subjects| subjects.iter().filter_map(|s| s.as_str()).collect::<Vec<_>>().join(", "))
+        .unwrap_or_default();
+    let first_publish_date = detail["first_publish_date"].as_str().unwrap_or("Unknown");
+
+    format!(
+        "Description: {}\n\nSubjects: {}\n\nFirst published: {}",
+        if description.is_empty() { "N/A" } else { &description },
+        if subjects.is_empty() { "N/A" } else { &subjects },
+        first_publish_date
+    )
+}